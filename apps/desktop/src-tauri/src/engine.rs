@@ -0,0 +1,119 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+const DEFAULT_PORT: u16 = 8765;
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A caption/translation frame as produced by the engine over the socket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CaptionFrame {
+    #[serde(flatten)]
+    payload: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum EngineStatus {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+/// Holds the handle of the background task currently driving the engine
+/// connection, if one is running, so `stop_engine` can cancel it.
+pub struct EngineState(Mutex<Option<tauri::async_runtime::JoinHandle<()>>>);
+
+impl EngineState {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+}
+
+/// Broadcasts `event` with `payload` to every open window so the main window
+/// and the overlay stay in sync without each opening its own socket.
+fn broadcast<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    for (_, window) in app.windows() {
+        let _ = window.emit(event, payload.clone());
+    }
+}
+
+/// Connects to the engine and relays frames until the connection drops, then
+/// returns so the caller can decide whether to reconnect.
+async fn run_connection(app: &AppHandle, url: &str) -> Result<(), String> {
+    let (ws_stream, _) = connect_async(url).await.map_err(|e| e.to_string())?;
+    broadcast(app, "engine-status", EngineStatus::Connected);
+
+    let (_, mut read) = ws_stream.split();
+    while let Some(message) = read.next().await {
+        let message = message.map_err(|e| e.to_string())?;
+        if let Message::Text(text) = message {
+            match serde_json::from_str::<CaptionFrame>(&text) {
+                Ok(frame) => broadcast(app, "caption", frame.payload),
+                Err(e) => eprintln!("failed to parse caption frame: {}", e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Connects to the engine's websocket, forever reconnecting with exponential
+/// backoff on failure, and relays caption frames and connection status to
+/// every window.
+async fn engine_loop(app: AppHandle, port: u16) {
+    let url = format!("ws://127.0.0.1:{}", port);
+    let mut backoff = Duration::from_secs(1);
+
+    loop {
+        broadcast(&app, "engine-status", EngineStatus::Connecting);
+
+        match run_connection(&app, &url).await {
+            Ok(()) => backoff = Duration::from_secs(1),
+            Err(e) => eprintln!("engine connection error: {}", e),
+        }
+
+        broadcast(&app, "engine-status", EngineStatus::Disconnected);
+
+        tokio::time::sleep(backoff).await;
+        backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+    }
+}
+
+/// Starts a background task that owns the engine websocket connection and
+/// fans caption/translation frames out to every window. Stops any
+/// previously running connection first.
+#[tauri::command]
+pub fn start_engine(app: AppHandle, port: Option<u16>) -> Result<(), String> {
+    let state = app.state::<EngineState>();
+    let mut running = state.0.lock().unwrap();
+
+    if let Some(handle) = running.take() {
+        handle.abort();
+    }
+
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let handle = tauri::async_runtime::spawn(engine_loop(app.clone(), port));
+    *running = Some(handle);
+
+    Ok(())
+}
+
+/// Stops the background engine connection, if one is running.
+#[tauri::command]
+pub fn stop_engine(app: AppHandle) -> Result<(), String> {
+    let state = app.state::<EngineState>();
+    let mut running = state.0.lock().unwrap();
+
+    if let Some(handle) = running.take() {
+        handle.abort();
+        broadcast(&app, "engine-status", EngineStatus::Disconnected);
+    }
+
+    Ok(())
+}
@@ -3,7 +3,23 @@
     windows_subsystem = "windows"
 )]
 
-use tauri::Manager;
+use std::sync::Mutex;
+
+use tauri::{GlobalShortcutManager, Manager};
+
+mod engine;
+mod overlay_state;
+
+#[cfg(feature = "system-tray")]
+mod tray;
+
+use overlay_state::OverlayLayoutState;
+
+const DEFAULT_OVERLAY_HOTKEY: &str = "CmdOrCtrl+Shift+O";
+
+/// Tracks the accelerator currently bound to the overlay toggle so it can be
+/// unregistered before a new one is registered in its place.
+struct OverlayHotkeyState(Mutex<Option<String>>);
 
 #[tauri::command]
 fn get_engine_url(port: Option<u16>) -> String {
@@ -11,42 +27,213 @@ fn get_engine_url(port: Option<u16>) -> String {
     format!("ws://127.0.0.1:{}", p)
 }
 
+/// Builds the overlay window, parented to `parent_label` (defaulting to
+/// `"main"`) so it minimizes/restores and z-orders together with the rest of
+/// the app instead of floating independently. Parenting is applied on macOS
+/// and Windows only; on other platforms the overlay has no parent window.
 #[tauri::command]
-fn create_overlay_window(app: tauri::AppHandle) -> Result<(), String> {
+pub(crate) fn create_overlay_window(
+    app: tauri::AppHandle,
+    parent_label: Option<String>,
+) -> Result<(), String> {
     let existing = app.get_window("overlay");
     if existing.is_some() {
         return Ok(());
     }
 
-    tauri::WindowBuilder::new(&app, "overlay", tauri::WindowUrl::App("index.html".into()))
-        .title("EchoFlux Overlay")
-        .inner_size(600.0, 200.0)
-        .always_on_top(true)
-        .decorations(false)
-        .transparent(true)
-        .skip_taskbar(true)
-        .resizable(true)
-        .build()
+    let parent_label = parent_label.unwrap_or_else(|| "main".to_string());
+    #[allow(unused_variables)]
+    let parent = app.get_window(&parent_label);
+
+    let layout = overlay_state::load(&app);
+
+    let mut builder =
+        tauri::WindowBuilder::new(&app, "overlay", tauri::WindowUrl::App("index.html".into()))
+            .title("EchoFlux Overlay")
+            .inner_size(layout.inner_size.0, layout.inner_size.1)
+            .always_on_top(layout.always_on_top)
+            .decorations(false)
+            .transparent(true)
+            .skip_taskbar(true)
+            .resizable(true);
+
+    if let Some((x, y)) = layout.position {
+        builder = builder.position(x, y);
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Some(parent) = &parent {
+        builder = builder.parent_window(parent.ns_window().map_err(|e| e.to_string())?);
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(parent) = &parent {
+        builder = builder.owner_window(parent.hwnd().map_err(|e| e.to_string())?);
+    }
+
+    let window = builder.build().map_err(|e| e.to_string())?;
+
+    let click_through = layout.click_through;
+    *app.state::<OverlayLayoutState>().0.lock().unwrap() = layout;
+
+    let event_handle = app.clone();
+    let event_window = window.clone();
+    window.on_window_event(move |event| match event {
+        tauri::WindowEvent::Moved(position) => {
+            let Ok(scale_factor) = event_window.scale_factor() else {
+                return;
+            };
+            let logical = position.to_logical::<f64>(scale_factor);
+            let state = event_handle.state::<OverlayLayoutState>();
+            let mut layout = state.0.lock().unwrap();
+            layout.position = Some((logical.x, logical.y));
+        }
+        tauri::WindowEvent::Resized(size) => {
+            let Ok(scale_factor) = event_window.scale_factor() else {
+                return;
+            };
+            let logical = size.to_logical::<f64>(scale_factor);
+            let state = event_handle.state::<OverlayLayoutState>();
+            let mut layout = state.0.lock().unwrap();
+            layout.inner_size = (logical.width, logical.height);
+        }
+        tauri::WindowEvent::CloseRequested { .. } => {
+            // Moved/Resized only update the in-memory layout (they can fire
+            // on every pixel of an interactive drag); only persist to disk
+            // once, here, when the window actually closes.
+            let state = event_handle.state::<OverlayLayoutState>();
+            let layout = state.0.lock().unwrap();
+            overlay_state::save(&event_handle, &layout);
+        }
+        _ => {}
+    });
+
+    set_overlay_click_through(app, click_through)?;
+
+    Ok(())
+}
+
+/// Deletes the saved overlay layout so the next overlay window reverts to
+/// the default 600x200 geometry. Also clears the live `OverlayLayoutState`
+/// so an already-open overlay doesn't re-save the stale layout on close.
+#[tauri::command]
+pub(crate) fn reset_overlay_layout(app: tauri::AppHandle) -> Result<(), String> {
+    overlay_state::reset(&app)?;
+    *app.state::<OverlayLayoutState>().0.lock().unwrap() = overlay_state::OverlayLayout::default();
+    Ok(())
+}
+
+/// Toggles whether the overlay window intercepts mouse events. Click-through
+/// is the default so the overlay can sit on top of a game or video without
+/// stealing focus; callers temporarily disable it (e.g. while hovering a
+/// settings gear) so the overlay can be dragged or resized.
+#[tauri::command]
+pub(crate) fn set_overlay_click_through(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let window = app
+        .get_window("overlay")
+        .ok_or_else(|| "overlay window is not open".to_string())?;
+    window
+        .set_ignore_cursor_events(enabled)
         .map_err(|e| e.to_string())?;
 
+    let state = app.state::<OverlayLayoutState>();
+    let mut layout = state.0.lock().unwrap();
+    layout.click_through = enabled;
+    overlay_state::save(&app, &layout);
+
     Ok(())
 }
 
 #[tauri::command]
-fn close_overlay_window(app: tauri::AppHandle) -> Result<(), String> {
+pub(crate) fn close_overlay_window(app: tauri::AppHandle) -> Result<(), String> {
     if let Some(window) = app.get_window("overlay") {
         window.close().map_err(|e| e.to_string())?;
     }
     Ok(())
 }
 
+/// Closes the overlay if it exists, otherwise builds it. Shared by the
+/// global shortcut handler so the hotkey behaves the same as the frontend
+/// `invoke` calls.
+fn toggle_overlay_window(app: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_window("overlay") {
+        window.close().map_err(|e| e.to_string())
+    } else {
+        create_overlay_window(app.clone(), None)
+    }
+}
+
+/// Registers `accelerator` as the overlay toggle and unregisters the
+/// previous binding (if any) once the new one is confirmed working, so a
+/// bad accelerator string can't leave the user with no working hotkey.
+#[tauri::command]
+fn set_overlay_hotkey(app: tauri::AppHandle, accelerator: String) -> Result<(), String> {
+    let state = app.state::<OverlayHotkeyState>();
+    let mut current = state.0.lock().unwrap();
+    let mut shortcut_manager = app.global_shortcut_manager();
+
+    let handle = app.clone();
+    shortcut_manager
+        .register(&accelerator, move || {
+            if let Err(e) = toggle_overlay_window(&handle) {
+                eprintln!("failed to toggle overlay window: {}", e);
+            }
+        })
+        .map_err(|e| e.to_string())?;
+
+    if let Some(previous) = current.as_ref() {
+        if previous != &accelerator {
+            if let Err(e) = shortcut_manager.unregister(previous) {
+                eprintln!("failed to unregister previous overlay hotkey: {}", e);
+            }
+        }
+    }
+
+    *current = Some(accelerator);
+    Ok(())
+}
+
 fn main() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default()
+        .manage(OverlayHotkeyState(Mutex::new(None)))
+        .manage(engine::EngineState::new())
+        .manage(OverlayLayoutState::new())
+        .setup(|app| {
+            let handle = app.handle();
+            if let Err(e) = set_overlay_hotkey(handle, DEFAULT_OVERLAY_HOTKEY.to_string()) {
+                eprintln!("failed to register overlay hotkey: {}", e);
+            }
+
+            #[cfg(feature = "system-tray")]
+            tray::sync_menu_state(&handle);
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_engine_url,
             create_overlay_window,
             close_overlay_window,
-        ])
+            set_overlay_hotkey,
+            set_overlay_click_through,
+            reset_overlay_layout,
+            engine::start_engine,
+            engine::stop_engine,
+        ]);
+
+    #[cfg(feature = "system-tray")]
+    let builder = builder
+        .system_tray(tray::build())
+        .on_system_tray_event(tray::handle_event)
+        .on_window_event(|event| {
+            if event.window().label() == "main" {
+                if let tauri::WindowEvent::CloseRequested { api, .. } = event.event() {
+                    event.window().hide().ok();
+                    api.prevent_close();
+                }
+            }
+        });
+
+    builder
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
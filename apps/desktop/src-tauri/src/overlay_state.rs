@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+const STATE_FILE: &str = "overlay-state.json";
+const DEFAULT_INNER_SIZE: (f64, f64) = (600.0, 200.0);
+
+/// The overlay's persisted geometry and window flags, read back on the next
+/// launch so users don't have to reposition the captions bar every time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlayLayout {
+    pub position: Option<(f64, f64)>,
+    pub inner_size: (f64, f64),
+    pub always_on_top: bool,
+    pub click_through: bool,
+}
+
+impl Default for OverlayLayout {
+    fn default() -> Self {
+        Self {
+            position: None,
+            inner_size: DEFAULT_INNER_SIZE,
+            always_on_top: true,
+            click_through: true,
+        }
+    }
+}
+
+/// Holds the layout currently applied to the overlay window so event
+/// handlers can update it in place before persisting to disk.
+pub struct OverlayLayoutState(pub Mutex<OverlayLayout>);
+
+impl OverlayLayoutState {
+    pub fn new() -> Self {
+        Self(Mutex::new(OverlayLayout::default()))
+    }
+}
+
+fn state_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path_resolver()
+        .app_config_dir()
+        .map(|dir| dir.join(STATE_FILE))
+}
+
+/// Reads the saved overlay layout from disk, falling back to the default
+/// 600x200 geometry if nothing has been saved yet or the file is invalid.
+pub fn load(app: &AppHandle) -> OverlayLayout {
+    state_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Persists `layout` to the app config dir, creating it if necessary.
+pub fn save(app: &AppHandle, layout: &OverlayLayout) {
+    let Some(path) = state_path(app) else {
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("failed to create overlay state dir: {}", e);
+            return;
+        }
+    }
+
+    match serde_json::to_string_pretty(layout) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&path, json) {
+                eprintln!("failed to write overlay state: {}", e);
+            }
+        }
+        Err(e) => eprintln!("failed to serialize overlay state: {}", e),
+    }
+}
+
+/// Deletes the saved overlay layout so the next overlay window falls back to
+/// the default geometry.
+pub fn reset(app: &AppHandle) -> Result<(), String> {
+    if let Some(path) = state_path(app) {
+        if path.exists() {
+            fs::remove_file(path).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use tauri::{
+    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem,
+};
+
+use crate::overlay_state::{self, OverlayLayoutState};
+use crate::{close_overlay_window, create_overlay_window};
+
+const SHOW_OVERLAY: &str = "show_overlay";
+const HIDE_OVERLAY: &str = "hide_overlay";
+const QUIT: &str = "quit";
+
+/// Builds the tray menu. EchoFlux keeps running from the tray once the main
+/// window is closed, so "Quit" is the only way to fully exit.
+pub fn build() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new(SHOW_OVERLAY, "Show Overlay"))
+        .add_item(CustomMenuItem::new(HIDE_OVERLAY, "Hide Overlay"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new(QUIT, "Quit"));
+
+    SystemTray::new().with_menu(menu)
+}
+
+/// Reflects whether the overlay is currently open by enabling only the
+/// applicable one of the "Show"/"Hide" items (their titles stay the same).
+/// Called after every tray interaction and once at startup; the hotkey and
+/// the frontend commands do not call it, so opening or closing the overlay
+/// through them leaves the menu stale until the next tray interaction.
+pub fn sync_menu_state(app: &AppHandle) {
+    let overlay_open = app.get_window("overlay").is_some();
+    let tray_handle = app.tray_handle();
+    let _ = tray_handle
+        .get_item(SHOW_OVERLAY)
+        .set_enabled(!overlay_open);
+    let _ = tray_handle.get_item(HIDE_OVERLAY).set_enabled(overlay_open);
+}
+
+pub fn handle_event(app: &AppHandle, event: SystemTrayEvent) {
+    match event {
+        SystemTrayEvent::LeftClick { .. } => {
+            let result = if app.get_window("overlay").is_some() {
+                close_overlay_window(app.clone())
+            } else {
+                create_overlay_window(app.clone(), None)
+            };
+            if let Err(e) = result {
+                eprintln!("failed to toggle overlay window from tray: {}", e);
+            }
+            sync_menu_state(app);
+        }
+        SystemTrayEvent::MenuItemClick { id, .. } => {
+            let result = match id.as_str() {
+                SHOW_OVERLAY => create_overlay_window(app.clone(), None),
+                HIDE_OVERLAY => close_overlay_window(app.clone()),
+                QUIT => {
+                    let state = app.state::<OverlayLayoutState>();
+                    let layout = state.0.lock().unwrap();
+                    overlay_state::save(app, &layout);
+                    drop(layout);
+                    app.exit(0);
+                    Ok(())
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                eprintln!("failed to handle tray menu item {}: {}", id, e);
+            }
+            sync_menu_state(app);
+        }
+        _ => {}
+    }
+}